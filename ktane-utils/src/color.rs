@@ -0,0 +1,95 @@
+//! HSV color math backing the colorblind-accessible wire palette.
+//!
+//! The standard wire colors are bunched close together in hue (red, yellow, white and black all
+//! read as similar dark/warm tones once rendered as a thin strip), which makes them hard to tell
+//! apart for colorblind players. The accessible palette instead spreads hues evenly around the
+//! wheel and boosts saturation/value, so [`Rgb::from_hsv`] is what turns that spread back into
+//! the RGB values a renderer actually draws.
+
+/// A color in the HSV (hue/saturation/value) color space.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Hsv {
+    /// Hue, in degrees; wraps at `360.0`.
+    pub hue: f64,
+    /// Saturation, `0.0..=1.0`.
+    pub saturation: f64,
+    /// Value (brightness), `0.0..=1.0`.
+    pub value: f64,
+}
+
+/// An 8-bit-per-channel RGB color.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Converts `hsv` to RGB via the standard hue-sector decomposition: split the wheel into six
+    /// 60-degree sectors, find how far through the current one `hue` falls (`f`), and blend
+    /// `value` down towards black (`p`), towards the sector's secondary color (`q`), and towards
+    /// its primary color (`t`) accordingly.
+    pub fn from_hsv(hsv: Hsv) -> Rgb {
+        let Hsv { hue, saturation, value } = hsv;
+
+        if saturation <= 0.0 {
+            let level = (value.max(0.0).min(1.0) * 255.0).round() as u8;
+            return Rgb::new(level, level, level);
+        }
+
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let sector = hue.floor() as u32 % 6;
+        let f = hue - hue.floor();
+
+        let p = value * (1.0 - saturation);
+        let q = value * (1.0 - saturation * f);
+        let t = value * (1.0 - saturation * (1.0 - f));
+
+        let (r, g, b) = match sector {
+            0 => (value, t, p),
+            1 => (q, value, p),
+            2 => (p, value, t),
+            3 => (p, q, value),
+            4 => (t, p, value),
+            _ => (value, p, q),
+        };
+
+        Rgb::new(
+            (r * 255.0).round() as u8,
+            (g * 255.0).round() as u8,
+            (b * 255.0).round() as u8,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primary_hues_convert_exactly() {
+        const TESTS: &[(f64, f64, f64, (u8, u8, u8))] = &[
+            (0.0, 1.0, 1.0, (255, 0, 0)),
+            (120.0, 1.0, 1.0, (0, 255, 0)),
+            (240.0, 1.0, 1.0, (0, 0, 255)),
+            (360.0, 1.0, 1.0, (255, 0, 0)),
+        ];
+
+        for &(hue, saturation, value, expected) in TESTS {
+            let Rgb { r, g, b } = Rgb::from_hsv(Hsv { hue, saturation, value });
+            assert_eq!((r, g, b), expected);
+        }
+    }
+
+    #[test]
+    fn zero_saturation_is_a_shade_of_gray() {
+        let Rgb { r, g, b } = Rgb::from_hsv(Hsv { hue: 77.0, saturation: 0.0, value: 0.5 });
+        assert_eq!(r, g);
+        assert_eq!(g, b);
+    }
+}
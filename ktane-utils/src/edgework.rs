@@ -0,0 +1,206 @@
+//! Parsing and representing a bomb's edgework: the serial number, indicators, port plates and
+//! battery counts printed on the casing, in the compact format used by the Rule Seed community
+//! tools' test fixtures, e.g.
+//! `"2B 1H // *SIG // [Serial, Parallel] [Empty] [DVI, StereoRCA] // RE3SE6"`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumCount, EnumIter, EnumProperty, EnumString};
+
+/// The ports that can appear on a port plate.
+#[derive(Debug, Display, EnumString, EnumProperty, Copy, Clone, PartialEq, Eq, Hash, EnumCount, EnumIter, Serialize, Deserialize)]
+pub enum PortType {
+    DVI,
+    Parallel,
+    PS2,
+    RJ45,
+    Serial,
+    StereoRCA,
+}
+
+/// One of the port plates mounted on a bomb's edgework: either empty, or carrying one or more
+/// [`PortType`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct PortPlate(u8);
+
+impl PortPlate {
+    pub fn empty() -> Self {
+        PortPlate(0)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn has(self, port: PortType) -> bool {
+        self.0 & (1 << port as u8) != 0
+    }
+
+    fn insert(&mut self, port: PortType) {
+        self.0 |= 1 << port as u8;
+    }
+}
+
+/// The serial number printed on a bomb's edgework.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialNumber(String);
+
+impl SerialNumber {
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// The last digit in the serial number, which several vanilla rules key off the parity of.
+    pub fn last_digit(&self) -> u8 {
+        self.0
+            .chars()
+            .rev()
+            .find_map(|ch| ch.to_digit(10))
+            .expect("serial number must contain at least one digit") as u8
+    }
+}
+
+impl fmt::Display for SerialNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A single indicator on a bomb's edgework, e.g. a lit `SIG` or an unlit `FRQ`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Indicator {
+    pub label: String,
+    pub lit: bool,
+}
+
+/// The full edgework of a bomb: everything visible on its casing other than the modules
+/// themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edgework {
+    pub battery_count: u8,
+    pub holder_count: u8,
+    pub indicators: Vec<Indicator>,
+    pub port_plates: Vec<PortPlate>,
+    pub serial_number: SerialNumber,
+}
+
+/// An error parsing an [`Edgework`] from its compact string format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEdgeworkError(String);
+
+impl fmt::Display for ParseEdgeworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid edgework string: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseEdgeworkError {}
+
+fn invalid(reason: impl Into<String>) -> ParseEdgeworkError {
+    ParseEdgeworkError(reason.into())
+}
+
+impl FromStr for Edgework {
+    type Err = ParseEdgeworkError;
+
+    /// Parses the compact edgework DSL used by the Rule Seed tooling's test fixtures:
+    /// `"<batteries>B <holders>H // [indicators] // [port plates] // <serial>"`, where the
+    /// indicators and port plates sections are entirely omitted (rather than left blank) when
+    /// there are none.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<&str> = input.split("//").map(str::trim).collect();
+
+        let (counts, rest) = segments
+            .split_first()
+            .ok_or_else(|| invalid("empty edgework string"))?;
+        let (serial, middle) = rest
+            .split_last()
+            .ok_or_else(|| invalid("missing serial number section"))?;
+
+        let (battery_count, holder_count) = parse_counts(counts)?;
+
+        let mut indicators = Vec::new();
+        let mut port_plates = Vec::new();
+        for section in middle {
+            if section.contains('[') {
+                port_plates = parse_port_plates(section)?;
+            } else {
+                indicators = parse_indicators(section);
+            }
+        }
+
+        Ok(Edgework {
+            battery_count,
+            holder_count,
+            indicators,
+            port_plates,
+            serial_number: SerialNumber((*serial).to_owned()),
+        })
+    }
+}
+
+fn parse_counts(section: &str) -> Result<(u8, u8), ParseEdgeworkError> {
+    let mut counts = section.split_whitespace();
+    let batteries = counts.next().ok_or_else(|| invalid("missing battery count"))?;
+    let holders = counts.next().ok_or_else(|| invalid("missing holder count"))?;
+
+    if !batteries.ends_with('B') || !holders.ends_with('H') {
+        return Err(invalid(format!(
+            "expected counts like \"2B 1H\", got \"{}\"",
+            section
+        )));
+    }
+
+    let battery_count = batteries[..batteries.len() - 1]
+        .parse()
+        .map_err(|_| invalid(format!("invalid battery count: \"{}\"", batteries)))?;
+    let holder_count = holders[..holders.len() - 1]
+        .parse()
+        .map_err(|_| invalid(format!("invalid holder count: \"{}\"", holders)))?;
+
+    Ok((battery_count, holder_count))
+}
+
+fn parse_indicators(section: &str) -> Vec<Indicator> {
+    section
+        .split_whitespace()
+        .map(|token| {
+            if token.starts_with('*') {
+                Indicator { label: token[1..].to_owned(), lit: true }
+            } else {
+                Indicator { label: token.to_owned(), lit: false }
+            }
+        })
+        .collect()
+}
+
+fn parse_port_plates(section: &str) -> Result<Vec<PortPlate>, ParseEdgeworkError> {
+    section
+        .split(']')
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            if !group.starts_with('[') {
+                return Err(invalid(format!(
+                    "expected a port plate like \"[Serial]\", got \"{}\"",
+                    group
+                )));
+            }
+            let group = &group[1..];
+
+            if group == "Empty" {
+                return Ok(PortPlate::empty());
+            }
+
+            let mut plate = PortPlate::empty();
+            for port in group.split(',').map(str::trim) {
+                let port: PortType = port
+                    .parse()
+                    .map_err(|_| invalid(format!("unknown port type: \"{}\"", port)))?;
+                plate.insert(port);
+            }
+            Ok(plate)
+        })
+        .collect()
+}
@@ -1,11 +1,13 @@
 use crate::edgework::Edgework;
 use crate::random::{RuleseedRandom, VANILLA_SEED};
+use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use std::collections::HashMap;
+use std::sync::Mutex;
 use strum_macros::{Display, EnumCount, EnumIter, IntoStaticStr};
 
 /// Stores a full rule set for Wires.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RuleSet([RuleList; 4]);
 
 impl RuleSet {
@@ -208,6 +210,133 @@ impl RuleSet {
     pub fn evaluate(&self, edgework: &Edgework, wires: &[Color]) -> Solution {
         self[wires.len()].evaluate(edgework, wires)
     }
+
+    /// Like [`RuleSet::evaluate`], but resolves the winning [`Solution`] straight to the wire
+    /// index a defuser should cut.
+    pub fn solve(&self, edgework: &Edgework, wires: &[Color]) -> usize {
+        self.evaluate(edgework, wires)
+            .as_index(wires)
+            .expect("solution didn't resolve to a wire present on the module") as usize
+    }
+
+    /// Serialize this rule set to a JSON string.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserialize a rule set previously produced by [`RuleSet::to_json`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the rule set for `seed`, generating and caching it on the first call for that
+    /// seed and cloning the cached copy on every subsequent one. This avoids re-running the
+    /// weighted-random walk in [`RuleSet::new`] every time a module is evaluated.
+    pub fn cached(seed: u32) -> Self {
+        lazy_static::lazy_static! {
+            static ref CACHE: Mutex<HashMap<u32, RuleSet>> = Mutex::new(HashMap::new());
+        }
+
+        let mut cache = CACHE.lock().unwrap();
+        cache.entry(seed).or_insert_with(|| RuleSet::new(seed)).clone()
+    }
+
+    /// Compiles every wire-count bucket into a [`CompiledRuleList`], trading the one-off cost of
+    /// exhaustively walking each bucket's tiny (edgework, wire-coloring) space for O(1) lookups
+    /// afterwards. Worthwhile for batch solving, e.g. fuzzing many configurations against a
+    /// generated seed, or checking it for unreachable rules via
+    /// [`CompiledRuleSet::unreachable_rules`].
+    pub fn compile(&self) -> CompiledRuleSet {
+        CompiledRuleSet(array_init::array_init(|index| {
+            CompiledRuleList::compile(&self.0[index], index + Self::MIN_WIRES)
+        }))
+    }
+
+    /// Renders all four wire-count buckets as Graphviz decision flowcharts, one graph per
+    /// bucket, separated by blank lines.
+    pub fn to_dot(&self, kind: Kind) -> String {
+        (Self::MIN_WIRES..=Self::MAX_WIRES)
+            .map(|wire_count| self[wire_count].to_dot(wire_count, kind))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders all four wire-count buckets as ordered, human-readable manual text: one
+    /// "If …, cut …" line per rule, falling through to "Otherwise, cut …".
+    pub fn to_manual_text(&self) -> String {
+        (Self::MIN_WIRES..=Self::MAX_WIRES)
+            .map(|wire_count| self[wire_count].to_manual_text(wire_count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Serializes this rule set and deflate-compresses it into a short string suitable for
+    /// pasting in chat, so a group can share an exact rule set — including a hand-edited variant
+    /// not tied to any seed — without sending the full manual text.
+    pub fn export(&self) -> String {
+        use flate2::{write::DeflateEncoder, Compression};
+        use std::io::Write;
+
+        let json = self.to_json().expect("RuleSet always serializes to JSON");
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(json.as_bytes())
+            .expect("writing to an in-memory buffer can't fail");
+        let compressed = encoder.finish().expect("writing to an in-memory buffer can't fail");
+
+        base64::encode(&compressed)
+    }
+
+    /// Reconstructs a [`RuleSet`] previously produced by [`RuleSet::export`].
+    pub fn import(blob: &str) -> Result<Self, ImportError> {
+        use flate2::read::DeflateDecoder;
+        use std::io::Read;
+
+        let compressed = base64::decode(blob)?;
+        let mut json = String::new();
+        DeflateDecoder::new(&compressed[..]).read_to_string(&mut json)?;
+
+        Ok(Self::from_json(&json)?)
+    }
+}
+
+/// An error reconstructing a [`RuleSet`] with [`RuleSet::import`].
+#[derive(Debug)]
+pub enum ImportError {
+    Base64(base64::DecodeError),
+    Inflate(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Base64(err) => write!(f, "invalid rule set blob: {}", err),
+            ImportError::Inflate(err) => write!(f, "couldn't inflate rule set: {}", err),
+            ImportError::Json(err) => write!(f, "invalid rule set data: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<base64::DecodeError> for ImportError {
+    fn from(err: base64::DecodeError) -> Self {
+        ImportError::Base64(err)
+    }
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(err: std::io::Error) -> Self {
+        ImportError::Inflate(err)
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(err: serde_json::Error) -> Self {
+        ImportError::Json(err)
+    }
 }
 
 use std::ops::Index;
@@ -220,8 +349,32 @@ impl Index<usize> for RuleSet {
     }
 }
 
+/// Selects between a directed decision flowchart and an undirected graph layout when rendering
+/// a [`RuleList`] with [`RuleList::to_dot`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Digraph,
+    Graph,
+}
+
+impl Kind {
+    fn keyword(self) -> &'static str {
+        match self {
+            Kind::Digraph => "digraph",
+            Kind::Graph => "graph",
+        }
+    }
+
+    fn edge_operator(self) -> &'static str {
+        match self {
+            Kind::Digraph => "->",
+            Kind::Graph => "--",
+        }
+    }
+}
+
 /// Represents the rules for a particular wire count.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RuleList {
     pub rules: SmallVec<[Rule; 4]>,
     /// The solution in case none of the rules applies.
@@ -237,11 +390,76 @@ impl RuleList {
             .next()
             .unwrap_or(self.otherwise)
     }
+
+    /// Renders this rule list as a Graphviz decision flowchart: one diamond node per rule
+    /// showing its conjoined queries, a "yes" edge to a terminal box with the rule's
+    /// `Solution`, and a "no" edge falling through to the next rule, terminating in the
+    /// `otherwise` solution.
+    pub fn to_dot(&self, wire_count: usize, kind: Kind) -> String {
+        let mut dot = format!(
+            "{} \"wires_{}\" {{\n    label=\"Wires ({} wires)\";\n",
+            kind.keyword(),
+            wire_count,
+            wire_count,
+        );
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            dot += &format!(
+                "    rule{} [shape=diamond, label=\"{}?\"];\n",
+                index,
+                rule.describe_queries(),
+            );
+            dot += &format!(
+                "    solution{} [shape=box, label=\"{}\"];\n",
+                index, rule.solution,
+            );
+            dot += &format!(
+                "    rule{0} {1} solution{0} [label=\"yes\"];\n",
+                index,
+                kind.edge_operator(),
+            );
+
+            let fallthrough = if index + 1 < self.rules.len() {
+                format!("rule{}", index + 1)
+            } else {
+                dot += &format!("    otherwise [shape=box, label=\"{}\"];\n", self.otherwise);
+                "otherwise".to_owned()
+            };
+
+            dot += &format!(
+                "    rule{} {} {} [label=\"no\"];\n",
+                index,
+                kind.edge_operator(),
+                fallthrough,
+            );
+        }
+
+        if self.rules.is_empty() {
+            dot += &format!("    otherwise [shape=box, label=\"{}\"];\n", self.otherwise);
+        }
+
+        dot += "}\n";
+        dot
+    }
+
+    /// Renders this rule list as ordered manual text: one "If …, cut …" line per rule, falling
+    /// through in order to "Otherwise, cut …".
+    pub fn to_manual_text(&self, wire_count: usize) -> String {
+        let mut text = format!("{} wires:\n", wire_count);
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let prefix = if index == 0 { "If" } else { "Otherwise, if" };
+            text += &format!("{} {}, {}.\n", prefix, rule.describe_queries(), rule.solution);
+        }
+
+        text += &format!("Otherwise, {}.\n", self.otherwise);
+        text
+    }
 }
 
 /// Represents a single sentence in the manual. If all `queries` are met, the `solution` applies
 /// (except earlier rules take precedence)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Rule {
     pub queries: SmallVec<[Query; 2]>,
     pub solution: Solution,
@@ -252,6 +470,16 @@ impl Rule {
         self.queries.iter().all(|query| query.evaluate(edgework, wires))
     }
 
+    /// Like [`Rule::evaluate`], but against a synthetic [`EdgeworkPoint`] rather than a real
+    /// [`Edgework`]. Used by [`RuleList::analyze`] to walk the bounded analysis space instead of
+    /// the infinite space of real edgework.
+    fn matches_point(&self, edgework: &EdgeworkPoint, wires: &[Color]) -> bool {
+        self.queries.iter().all(|query| match query {
+            Query::Edgework(query) => edgework.matches(*query),
+            Query::Wire(query) => query.evaluate(wires),
+        })
+    }
+
     fn is_valid(&self) -> bool {
         // A single query can never be redundant.
         if self.queries.len() == 1 {
@@ -285,12 +513,23 @@ impl Rule {
 
         return true;
     }
+
+    /// Joins this rule's queries into a single sentence, e.g. "there are no red wires and the
+    /// last wire is blue". `Rule` has no `Display` of its own because a bare rule, in isolation,
+    /// doesn't read as a full sentence the way a diamond node in a flowchart needs it to.
+    fn describe_queries(&self) -> String {
+        self.queries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
 }
 
 use crate::edgework::PortType;
 
 /// A single condition of a rule
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Query {
     Edgework(EdgeworkQuery),
     Wire(WireQuery),
@@ -376,8 +615,18 @@ impl Query {
     }
 }
 
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Query::*;
+        match self {
+            Edgework(query) => query.fmt(f),
+            Wire(query) => query.fmt(f),
+        }
+    }
+}
+
 /// A condition pertaining to the edgework of a bomb
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EdgeworkQuery {
     SerialStartsWithLetter,
     SerialOdd,
@@ -413,8 +662,387 @@ impl EdgeworkQuery {
     }
 }
 
-/// A condition pertaining to the colors of the wires on a module
+/// A point in the tiny space [`RuleList::analyze`] enumerates: every `EdgeworkQuery` only ever
+/// depends on one of these independent booleans, never on the rest of a real [`Edgework`]
+/// (serial number digits, exact port plate layout, etc.), so this is all reachability analysis
+/// and the reverse solver need to stand in for one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeworkPoint {
+    serial_starts_with_letter: bool,
+    serial_odd: bool,
+    has_empty_port_plate: bool,
+    port_present: HashMap<PortType, bool>,
+}
+
+impl EdgeworkPoint {
+    /// Enumerates every possible assignment of the independent edgework booleans.
+    fn all() -> impl Iterator<Item = EdgeworkPoint> {
+        use strum::IntoEnumIterator;
+        let port_types: Vec<PortType> = PortType::iter().collect();
+        let port_combo_count = 1usize << port_types.len();
+
+        (0..8usize).flat_map(move |bits| {
+            let port_types = port_types.clone();
+            (0..port_combo_count).map(move |port_bits| EdgeworkPoint {
+                serial_starts_with_letter: bits & 1 != 0,
+                serial_odd: bits & 2 != 0,
+                has_empty_port_plate: bits & 4 != 0,
+                port_present: port_types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &port)| (port, port_bits & (1 << index) != 0))
+                    .collect(),
+            })
+        })
+    }
+
+    fn matches(&self, query: EdgeworkQuery) -> bool {
+        use self::EdgeworkQuery::*;
+        match query {
+            SerialStartsWithLetter => self.serial_starts_with_letter,
+            SerialOdd => self.serial_odd,
+            HasEmptyPortPlate => self.has_empty_port_plate,
+            PortPresent(port) => *self.port_present.get(&port).unwrap_or(&false),
+        }
+    }
+
+    /// Projects a real [`Edgework`] down onto the same independent booleans as every other point
+    /// in this space, by reusing [`EdgeworkQuery::evaluate`] for each one.
+    fn from_edgework(edgework: &Edgework) -> EdgeworkPoint {
+        use strum::IntoEnumIterator;
+        EdgeworkPoint {
+            serial_starts_with_letter: EdgeworkQuery::SerialStartsWithLetter.evaluate(edgework),
+            serial_odd: EdgeworkQuery::SerialOdd.evaluate(edgework),
+            has_empty_port_plate: EdgeworkQuery::HasEmptyPortPlate.evaluate(edgework),
+            port_present: PortType::iter()
+                .map(|port| (port, EdgeworkQuery::PortPresent(port).evaluate(edgework)))
+                .collect(),
+        }
+    }
+}
+
+/// Enumerates every wire coloring for a fixed `wire_count`.
+fn wire_colorings(wire_count: usize) -> impl Iterator<Item = Vec<Color>> {
+    use strum::IntoEnumIterator;
+    let colors: Vec<Color> = Color::iter().collect();
+    let total = colors.len().pow(wire_count as u32);
+
+    (0..total).map(move |index| {
+        let mut index = index;
+        let mut wires = Vec::with_capacity(wire_count);
+        for _ in 0..wire_count {
+            wires.push(colors[index % colors.len()]);
+            index /= colors.len();
+        }
+        wires
+    })
+}
+
+/// The result of running [`RuleList::analyze`] over the full (edgework, wire-coloring) space for
+/// one wire count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleReport {
+    /// Indices of rules that are never the first rule to match at any point in the space:
+    /// earlier rules subsume every point they could have matched.
+    pub unreachable_rules: Vec<usize>,
+    /// Indices of rules whose `Solution` is identical to the list's `otherwise` fallback, and
+    /// whose matched points would all still resolve to `otherwise` (rather than some other
+    /// rule) if the rule were removed.
+    pub redundant_rules: Vec<usize>,
+    /// Whether any point in the space falls through every rule to `otherwise`.
+    pub otherwise_reachable: bool,
+}
+
+impl RuleList {
+    /// Performs whole-list reachability analysis: since the domain is tiny (each wire is one of
+    /// `COLOR_COUNT` colors, and edgework reduces to a handful of independent booleans), this
+    /// enumerates every (edgework, wire-coloring) point, evaluates the rules in order the way a
+    /// liveness/dataflow pass walks a program, and records which rules never win and which are
+    /// only ever reached via a fallback identical to `otherwise`.
+    pub fn analyze(&self, wire_count: usize) -> RuleReport {
+        let mut reachable = vec![false; self.rules.len()];
+        let mut shadowed_by_later_rule = vec![false; self.rules.len()];
+        let mut otherwise_reachable = false;
+
+        for edgework in EdgeworkPoint::all() {
+            for wires in wire_colorings(wire_count) {
+                match self.rules.iter().position(|rule| rule.matches_point(&edgework, &wires)) {
+                    Some(index) => {
+                        reachable[index] = true;
+                        if self.rules[index + 1..]
+                            .iter()
+                            .any(|rule| rule.matches_point(&edgework, &wires))
+                        {
+                            shadowed_by_later_rule[index] = true;
+                        }
+                    }
+                    None => otherwise_reachable = true,
+                }
+            }
+        }
+
+        let unreachable_rules = reachable
+            .iter()
+            .enumerate()
+            .filter(|&(_, &hit)| !hit)
+            .map(|(index, _)| index)
+            .collect();
+
+        let redundant_rules = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|&(index, rule)| {
+                reachable[index] && !shadowed_by_later_rule[index] && rule.solution == self.otherwise
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        RuleReport {
+            unreachable_rules,
+            redundant_rules,
+            otherwise_reachable,
+        }
+    }
+
+    /// Returns every wire coloring of `wire_count` wires that, evaluated against this list under
+    /// the fixed `edgework`, resolves to cutting the `target`-th wire. Useful for constructing a
+    /// bomb that guarantees "cut wire N" under a chosen seed and edgework.
+    pub fn inputs_for_solution(
+        &self,
+        wire_count: usize,
+        edgework: &Edgework,
+        target: u8,
+    ) -> Vec<Vec<Color>> {
+        wire_colorings(wire_count)
+            .filter(|wires| self.evaluate(edgework, wires).as_index(wires) == Some(target))
+            .collect()
+    }
+
+    /// Like [`RuleList::inputs_for_solution`], but also varies the edgework over the same
+    /// bounded space [`RuleList::analyze`] enumerates, returning every (edgework, wire-coloring)
+    /// pair that resolves to cutting the `target`-th wire.
+    pub fn inputs_for_solution_any_edgework(
+        &self,
+        wire_count: usize,
+        target: u8,
+    ) -> Vec<(EdgeworkPoint, Vec<Color>)> {
+        let mut matches = Vec::new();
+
+        for edgework in EdgeworkPoint::all() {
+            for wires in wire_colorings(wire_count) {
+                let first_match = self.rules.iter().find(|rule| rule.matches_point(&edgework, &wires));
+                let solution = first_match.map_or(self.otherwise, |rule| rule.solution);
+
+                if solution.as_index(&wires) == Some(target) {
+                    matches.push((edgework.clone(), wires));
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// A single dimension a compiled rule list's decode table can be indexed over. Only the
+/// dimensions a [`RuleList`]'s own queries actually touch are included, so real tables stay tiny
+/// even though the full space has many more independent variables than this.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Feature {
+    /// How many wires of this color are present, clamped to 0, 1, or 2-or-more.
+    ColorCount(Color),
+    /// The color of the last wire.
+    LastWireColor,
+    SerialOdd,
+    SerialStartsWithLetter,
+    HasEmptyPortPlate,
+    PortPresent(PortType),
+}
+
+impl Feature {
+    /// The `Feature` a given `Query` depends on.
+    fn of(query: Query) -> Feature {
+        match query {
+            Query::Edgework(EdgeworkQuery::SerialOdd) => Feature::SerialOdd,
+            Query::Edgework(EdgeworkQuery::SerialStartsWithLetter) => Feature::SerialStartsWithLetter,
+            Query::Edgework(EdgeworkQuery::HasEmptyPortPlate) => Feature::HasEmptyPortPlate,
+            Query::Edgework(EdgeworkQuery::PortPresent(port)) => Feature::PortPresent(port),
+            Query::Wire(WireQuery { query_type: WireQueryType::LastWireIs, .. }) => Feature::LastWireColor,
+            Query::Wire(WireQuery { color, .. }) => Feature::ColorCount(color),
+        }
+    }
+
+    /// How many distinct values this feature can take.
+    fn cardinality(self) -> u32 {
+        match self {
+            Feature::ColorCount(_) => 3,
+            Feature::LastWireColor => COLOR_COUNT as u32,
+            Feature::SerialOdd
+            | Feature::SerialStartsWithLetter
+            | Feature::HasEmptyPortPlate
+            | Feature::PortPresent(_) => 2,
+        }
+    }
+
+    /// This feature's value for a given point in the space, in `0..self.cardinality()`.
+    fn value(self, edgework: &EdgeworkPoint, wires: &[Color]) -> u32 {
+        use strum::IntoEnumIterator;
+        match self {
+            Feature::ColorCount(color) => {
+                wires.iter().filter(|&&wire| wire == color).count().min(2) as u32
+            }
+            Feature::LastWireColor => Color::iter()
+                .position(|color| color == *wires.last().expect("wire_count is never 0"))
+                .expect("every Color appears in Color::iter()") as u32,
+            Feature::SerialOdd => edgework.matches(EdgeworkQuery::SerialOdd) as u32,
+            Feature::SerialStartsWithLetter => {
+                edgework.matches(EdgeworkQuery::SerialStartsWithLetter) as u32
+            }
+            Feature::HasEmptyPortPlate => edgework.matches(EdgeworkQuery::HasEmptyPortPlate) as u32,
+            Feature::PortPresent(port) => edgework.matches(EdgeworkQuery::PortPresent(port)) as u32,
+        }
+    }
+}
+
+/// Every distinct `Feature` a `RuleList`'s queries reference, in first-seen order.
+fn relevant_features(rules: &RuleList) -> Vec<Feature> {
+    let mut features = Vec::new();
+    for rule in &rules.rules {
+        for &query in &rule.queries {
+            let feature = Feature::of(query);
+            if !features.contains(&feature) {
+                features.push(feature);
+            }
+        }
+    }
+    features
+}
+
+/// Packs a point's values along `features` into a single mixed-radix index.
+fn feature_key(features: &[Feature], edgework: &EdgeworkPoint, wires: &[Color]) -> usize {
+    features.iter().fold(0, |key, &feature| {
+        key * feature.cardinality() as usize + feature.value(edgework, wires) as usize
+    })
+}
+
+/// A single entry in a [`CompiledRuleList`]'s decode table.
+///
+/// A feature key only captures the dimensions a rule list's queries actually reference, not full
+/// wire positions. A by-position [`Solution`] (`TheOneOfColor`, `FirstOfColor`, `LastOfColor`) can
+/// resolve to different cuts for two wire colorings that land on the same key — e.g. two boards
+/// both matching "exactly one red wire" with the red wire at a different index. `Ambiguous` marks
+/// those keys so [`CompiledRuleList::solve`] can fall back to walking the rule chain instead of
+/// handing back whichever cut happened to be seen first while compiling.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum TableEntry {
+    Unique(u8),
+    Ambiguous,
+}
+
+/// A [`RuleList`] compiled into a flat decode table, indexed by [`feature_key`]: a precomputed ROM
+/// in place of walking the rule chain, in the same spirit as a table-driven instruction decoder
+/// indexed by opcode bits rather than a chain of conditionals. Keys a by-position solution makes
+/// ambiguous (see [`TableEntry`]) fall back to `rules` itself.
+#[derive(Debug, Clone)]
+pub struct CompiledRuleList {
+    features: Vec<Feature>,
+    table: Vec<TableEntry>,
+    rules: RuleList,
+    /// Indices of rules that no feature-key combination ever selects, discovered for free while
+    /// walking the space to build `table`.
+    unreachable_rules: Vec<usize>,
+}
+
+impl CompiledRuleList {
+    fn compile(rules: &RuleList, wire_count: usize) -> CompiledRuleList {
+        let features = relevant_features(rules);
+        let size: usize = features.iter().map(|feature| feature.cardinality() as usize).product();
+        let mut table: Vec<Option<TableEntry>> = vec![None; size];
+        let mut reached = vec![false; rules.rules.len()];
+
+        for edgework in EdgeworkPoint::all() {
+            for wires in wire_colorings(wire_count) {
+                let key = feature_key(&features, &edgework, &wires);
+                let matched = rules.rules.iter().position(|rule| rule.matches_point(&edgework, &wires));
+                if let Some(index) = matched {
+                    reached[index] = true;
+                }
+
+                let solution = matched.map_or(rules.otherwise, |index| rules.rules[index].solution);
+                let cut = solution
+                    .as_index(&wires)
+                    .expect("a generated rule's solution always resolves to a concrete wire");
+
+                table[key] = Some(match table[key] {
+                    None => TableEntry::Unique(cut),
+                    Some(TableEntry::Unique(existing)) if existing == cut => TableEntry::Unique(cut),
+                    Some(TableEntry::Unique(_)) | Some(TableEntry::Ambiguous) => TableEntry::Ambiguous,
+                });
+            }
+        }
+
+        let unreachable_rules = reached
+            .iter()
+            .enumerate()
+            .filter(|&(_, &hit)| !hit)
+            .map(|(index, _)| index)
+            .collect();
+
+        CompiledRuleList {
+            features,
+            table: table.into_iter().map(|entry| entry.unwrap_or(TableEntry::Unique(0))).collect(),
+            rules: rules.clone(),
+            unreachable_rules,
+        }
+    }
+
+    /// Resolves the cut index for `edgework`/`wires` with a single table lookup, falling back to
+    /// walking the rule chain the way [`RuleList::evaluate`] does for keys a by-position solution
+    /// made ambiguous (see [`TableEntry`]).
+    pub fn solve(&self, edgework: &Edgework, wires: &[Color]) -> usize {
+        let point = EdgeworkPoint::from_edgework(edgework);
+        match self.table[feature_key(&self.features, &point, wires)] {
+            TableEntry::Unique(cut) => cut as usize,
+            TableEntry::Ambiguous => self
+                .rules
+                .evaluate(edgework, wires)
+                .as_index(wires)
+                .expect("a generated rule's solution always resolves to a concrete wire")
+                as usize,
+        }
+    }
+
+    /// Indices of rules that no reachable feature-key combination ever selects.
+    pub fn unreachable_rules(&self) -> &[usize] {
+        &self.unreachable_rules
+    }
+}
+
+/// A [`RuleSet`] compiled into one [`CompiledRuleList`] per wire-count bucket. See
+/// [`RuleSet::compile`].
+#[derive(Debug, Clone)]
+pub struct CompiledRuleSet([CompiledRuleList; 4]);
+
+impl CompiledRuleSet {
+    /// Resolves the cut index for `edgework`/`wires`, picking the bucket by `wires.len()`.
+    pub fn solve(&self, edgework: &Edgework, wires: &[Color]) -> usize {
+        self.0[wires.len() - RuleSet::MIN_WIRES].solve(edgework, wires)
+    }
+
+    /// Indices of rules in the `wire_count` bucket that no reachable feature-key combination
+    /// ever selects, or `None` if `wire_count` isn't one of [`RuleSet::MIN_WIRES`]..=
+    /// [`RuleSet::MAX_WIRES`].
+    pub fn unreachable_rules(&self, wire_count: usize) -> Option<&[usize]> {
+        if (RuleSet::MIN_WIRES..=RuleSet::MAX_WIRES).contains(&wire_count) {
+            Some(self.0[wire_count - RuleSet::MIN_WIRES].unreachable_rules())
+        } else {
+            None
+        }
+    }
+}
+
+/// A condition pertaining to the colors of the wires on a module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WireQuery {
     query_type: WireQueryType,
     color: Color,
@@ -490,7 +1118,7 @@ impl WireQuery {
 }
 
 /// The action the player should take to defuse a particular wire module
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Solution {
     /// Cut the n-th wire. 0-indexed
     Index(u8),
@@ -570,7 +1198,7 @@ impl Solution {
 }
 
 /// The colors a wire can have
-#[derive(Debug, Display, Copy, Clone, IntoStaticStr, EnumCount, EnumIter, PartialEq, Eq)]
+#[derive(Debug, Display, Copy, Clone, IntoStaticStr, EnumCount, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
 #[strum(serialize_all = "snake_case")]
 pub enum Color {
     Black,
@@ -580,6 +1208,72 @@ pub enum Color {
     Yellow,
 }
 
+/// Which palette to render wires with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// The game's standard wire colors.
+    Standard,
+    /// Hues spread evenly around the wheel with boosted saturation/value, plus a one-letter
+    /// label overlaid on each wire, so adjacent wires stay distinguishable for colorblind
+    /// players regardless of how their color vision remaps hue.
+    ColorblindAccessible,
+}
+
+impl Color {
+    /// This color's RGB value under `palette`.
+    pub fn rgb(self, palette: Palette) -> crate::color::Rgb {
+        match palette {
+            Palette::Standard => self.standard_rgb(),
+            Palette::ColorblindAccessible => self.accessible_rgb(),
+        }
+    }
+
+    fn standard_rgb(self) -> crate::color::Rgb {
+        use self::Color::*;
+        use crate::color::Rgb;
+        match self {
+            Black => Rgb::new(0x20, 0x20, 0x20),
+            Blue => Rgb::new(0x20, 0x60, 0xff),
+            Red => Rgb::new(0xe0, 0x20, 0x20),
+            White => Rgb::new(0xf0, 0xf0, 0xf0),
+            Yellow => Rgb::new(0xe0, 0xd0, 0x20),
+        }
+    }
+
+    /// This color's hue in the accessible palette: spread evenly around the wheel in
+    /// [`Color::iter`](strum::IntoEnumIterator::iter) order, rather than the bunched-up hues the
+    /// standard palette uses.
+    fn accessible_hue(self) -> f64 {
+        use strum::IntoEnumIterator;
+        let index = Color::iter()
+            .position(|color| color == self)
+            .expect("every Color appears in Color::iter()");
+        360.0 * index as f64 / COLOR_COUNT as f64
+    }
+
+    fn accessible_rgb(self) -> crate::color::Rgb {
+        use crate::color::{Hsv, Rgb};
+        Rgb::from_hsv(Hsv {
+            hue: self.accessible_hue(),
+            saturation: 0.9,
+            value: 0.95,
+        })
+    }
+
+    /// A single letter identifying this color, overlaid on each wire when rendering with
+    /// [`Palette::ColorblindAccessible`].
+    pub fn accessible_label(self) -> char {
+        use self::Color::*;
+        match self {
+            Black => 'K',
+            Blue => 'B',
+            Red => 'R',
+            White => 'W',
+            Yellow => 'Y',
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Color::*;
@@ -620,6 +1314,204 @@ mod tests {
         assert_eq!(format!("{}", Black), "black");
     }
 
+    #[test]
+    fn rule_list_to_dot_contains_every_rule_and_terminator() {
+        let rules = RuleSet::new(1);
+        let list = rules.get(3).unwrap();
+        let dot = list.to_dot(3, Kind::Digraph);
+
+        assert!(dot.starts_with("digraph \"wires_3\""));
+        assert!(dot.contains("->"));
+        assert!(dot.contains("otherwise"));
+        for index in 0..list.rules.len() {
+            assert!(dot.contains(&format!("rule{}", index)));
+        }
+    }
+
+    #[test]
+    fn analyze_flags_an_unreachable_duplicate_rule() {
+        let condition = || smallvec![Query::Wire(WireQuery {
+            query_type: WireQueryType::ExactlyZeroOfColor,
+            color: Red,
+        })];
+
+        let list = RuleList {
+            rules: smallvec![
+                Rule { queries: condition(), solution: Solution::Index(0) },
+                Rule { queries: condition(), solution: Solution::Index(1) },
+            ],
+            otherwise: Solution::Index(2),
+        };
+
+        let report = list.analyze(3);
+        assert_eq!(report.unreachable_rules, vec![1]);
+        assert!(report.otherwise_reachable);
+    }
+
+    #[test]
+    fn analyze_flags_a_rule_redundant_with_otherwise() {
+        let list = RuleList {
+            rules: smallvec![Rule {
+                queries: smallvec![Query::Wire(WireQuery {
+                    query_type: WireQueryType::ExactlyZeroOfColor,
+                    color: Red,
+                })],
+                solution: Solution::Index(2),
+            }],
+            otherwise: Solution::Index(2),
+        };
+
+        let report = list.analyze(3);
+        assert!(report.unreachable_rules.is_empty());
+        assert_eq!(report.redundant_rules, vec![0]);
+    }
+
+    #[test]
+    fn compiled_rule_list_matches_rule_list_evaluate() {
+        let list = RuleList {
+            rules: smallvec![Rule {
+                queries: smallvec![Query::Wire(WireQuery {
+                    query_type: WireQueryType::ExactlyZeroOfColor,
+                    color: Red,
+                })],
+                solution: Solution::Index(0),
+            }],
+            otherwise: Solution::Index(1),
+        };
+
+        let compiled = CompiledRuleList::compile(&list, 3);
+        let edgework = "0B 0H // KT4NE8".parse::<Edgework>().unwrap();
+
+        for wires in wire_colorings(3) {
+            let expected = list.evaluate(&edgework, &wires).as_index(&wires).unwrap() as usize;
+            assert_eq!(compiled.solve(&edgework, &wires), expected);
+        }
+    }
+
+    #[test]
+    fn compiled_rule_list_matches_rule_list_evaluate_for_by_position_solutions() {
+        let list = RuleList {
+            rules: smallvec![
+                Rule {
+                    queries: smallvec![Query::Wire(WireQuery {
+                        query_type: WireQueryType::ExactlyOneOfColor,
+                        color: Red,
+                    })],
+                    solution: Solution::TheOneOfColor(Red),
+                },
+                // Keeps `otherwise` to colorings with a blue wire, so `FirstOfColor(Blue)`
+                // always resolves.
+                Rule {
+                    queries: smallvec![Query::Wire(WireQuery {
+                        query_type: WireQueryType::ExactlyZeroOfColor,
+                        color: Blue,
+                    })],
+                    solution: Solution::Index(2),
+                },
+            ],
+            otherwise: Solution::FirstOfColor(Blue),
+        };
+
+        let compiled = CompiledRuleList::compile(&list, 3);
+        let edgework = "0B 0H // KT4NE8".parse::<Edgework>().unwrap();
+
+        // Several of these colorings share a feature key (e.g. "exactly one red wire") but
+        // resolve `TheOneOfColor`/`FirstOfColor` to different indices, which is exactly the
+        // aliasing `CompiledRuleList::solve` must fall back to `RuleList::evaluate` for.
+        for wires in wire_colorings(3) {
+            let expected = list.evaluate(&edgework, &wires).as_index(&wires).unwrap() as usize;
+            assert_eq!(compiled.solve(&edgework, &wires), expected);
+        }
+    }
+
+    #[test]
+    fn compile_flags_the_same_unreachable_rule_as_analyze() {
+        let condition = || smallvec![Query::Wire(WireQuery {
+            query_type: WireQueryType::ExactlyZeroOfColor,
+            color: Red,
+        })];
+
+        let list = RuleList {
+            rules: smallvec![
+                Rule { queries: condition(), solution: Solution::Index(0) },
+                Rule { queries: condition(), solution: Solution::Index(1) },
+            ],
+            otherwise: Solution::Index(2),
+        };
+
+        let compiled = CompiledRuleList::compile(&list, 3);
+        assert_eq!(compiled.unreachable_rules(), &[1]);
+    }
+
+    #[test]
+    fn inputs_for_solution_any_edgework_matches_forward_evaluation() {
+        let list = RuleList {
+            rules: smallvec![Rule {
+                queries: smallvec![Query::Wire(WireQuery {
+                    query_type: WireQueryType::ExactlyZeroOfColor,
+                    color: Red,
+                })],
+                solution: Solution::Index(0),
+            }],
+            otherwise: Solution::Index(1),
+        };
+
+        for (_, wires) in list.inputs_for_solution_any_edgework(3, 0) {
+            assert!(!wires.contains(&Red));
+        }
+
+        for (_, wires) in list.inputs_for_solution_any_edgework(3, 1) {
+            assert!(wires.contains(&Red));
+        }
+    }
+
+    #[test]
+    fn rule_set_json_round_trip() {
+        for seed in &[1, 2, 12345] {
+            let rules = RuleSet::new(*seed);
+            let round_tripped = RuleSet::from_json(&rules.to_json().unwrap()).unwrap();
+            assert_eq!(rules, round_tripped);
+        }
+    }
+
+    #[test]
+    fn rule_set_export_import_round_trip() {
+        for seed in &[1, 2, 12345] {
+            let rules = RuleSet::new(*seed);
+            let round_tripped = RuleSet::import(&rules.export()).unwrap();
+            assert_eq!(rules, round_tripped);
+        }
+    }
+
+    #[test]
+    fn manual_text_mentions_every_rule_and_the_fallback() {
+        let rules = RuleSet::new(1);
+        let list = rules.get(3).unwrap();
+        let text = list.to_manual_text(3);
+
+        assert!(text.starts_with("3 wires:\n"));
+        assert!(text.contains("Otherwise, "));
+        for rule in &list.rules {
+            assert!(text.contains(&rule.solution.to_string()));
+        }
+    }
+
+    #[test]
+    fn accessible_palette_gives_every_color_a_distinct_hue_and_label() {
+        use strum::IntoEnumIterator;
+
+        let mut hues: Vec<f64> = Color::iter().map(Color::accessible_hue).collect();
+        hues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for pair in hues.windows(2) {
+            assert!(pair[1] - pair[0] >= 360.0 / COLOR_COUNT as f64 - f64::EPSILON);
+        }
+
+        let mut labels: Vec<char> = Color::iter().map(Color::accessible_label).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        assert_eq!(labels.len(), COLOR_COUNT);
+    }
+
     #[test]
     fn wire_query_evaluate() {
         use super::WireQueryType::*;
@@ -643,29 +1535,28 @@ mod tests {
         }
     }
 
-    /*
     #[test]
     fn query_evaluate() {
-        use super::Query::*;
-        use super::PortType::*;
+        use super::EdgeworkQuery::*;
+        use crate::edgework::PortType::*;
 
         #[rustfmt::skip]
         const TESTS: &[(Option<&str>, Option<&[Color]>, Query, bool)] = &[
-            (Some("0B 0H // KT4NE8"), None, SerialStartsWithLetter, true),
-            (Some("0B 0H // 123AB4"), None, SerialStartsWithLetter, false),
-            (Some("0B 0H // KT4NE8"), None, SerialOdd, false),
-            (Some("0B 0H // KT4NE7"), None, SerialOdd, true),
-            (Some("0B 0H // [Empty] // KT4NE8"), None, HasEmptyPortPlate, true),
-            (Some("0B 0H // [Serial] [Empty] // KT4NE8"), None, HasEmptyPortPlate, true),
-            (Some("0B 0H // KT4NE8"), None, HasEmptyPortPlate, false),
-            (Some("0B 0H // [Serial] [RCA] // KT4NE8"), None, HasEmptyPortPlate, false),
-            (Some("0B 0H // [Serial] // KT4NE8"), None, PortPresent(Serial), true),
-            (Some("0B 0H // [Serial, Parallel] // KT4NE8"), None, PortPresent(Serial), true),
-            (Some("0B 0H // [Serial, Parallel] // KT4NE8"), None, PortPresent(Parallel), true),
-            (Some("0B 0H // [Parallel] [Empty] // KT4NE8"), None, PortPresent(Serial), false),
-            (Some("0B 0H // [Parallel] [Serial] // KT4NE8"), None, PortPresent(Serial), true),
-            (Some("0B 0H // [Serial] [Parallel] // KT4NE8"), None, PortPresent(Serial), true),
-            (Some("0B 0H // KT4NE8"), None, PortPresent(Serial), false),
+            (Some("0B 0H // KT4NE8"), None, Query::Edgework(SerialStartsWithLetter), true),
+            (Some("0B 0H // 123AB4"), None, Query::Edgework(SerialStartsWithLetter), false),
+            (Some("0B 0H // KT4NE8"), None, Query::Edgework(SerialOdd), false),
+            (Some("0B 0H // KT4NE7"), None, Query::Edgework(SerialOdd), true),
+            (Some("0B 0H // [Empty] // KT4NE8"), None, Query::Edgework(HasEmptyPortPlate), true),
+            (Some("0B 0H // [Serial] [Empty] // KT4NE8"), None, Query::Edgework(HasEmptyPortPlate), true),
+            (Some("0B 0H // KT4NE8"), None, Query::Edgework(HasEmptyPortPlate), false),
+            (Some("0B 0H // [Serial] [DVI] // KT4NE8"), None, Query::Edgework(HasEmptyPortPlate), false),
+            (Some("0B 0H // [Serial] // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), true),
+            (Some("0B 0H // [Serial, Parallel] // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), true),
+            (Some("0B 0H // [Serial, Parallel] // KT4NE8"), None, Query::Edgework(PortPresent(Parallel)), true),
+            (Some("0B 0H // [Parallel] [Empty] // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), false),
+            (Some("0B 0H // [Parallel] [Serial] // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), true),
+            (Some("0B 0H // [Serial] [Parallel] // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), true),
+            (Some("0B 0H // KT4NE8"), None, Query::Edgework(PortPresent(Serial)), false),
         ];
 
         for &(edgework, colors, query, expected) in TESTS {
@@ -676,17 +1567,17 @@ mod tests {
             let colors = colors.unwrap_or(&[Red, Black, Blue]);
             assert_eq!(query.evaluate(&edgework, colors), expected);
         }
-    }*/
+    }
 
     #[test]
-    #[ignore]
+    #[ignore] // `vanilla_ruleset` is still `unimplemented!()`.
     fn vanilla_rules() {
         let rules = RuleSet::new(VANILLA_SEED);
 
         for &(edgework, colors, expected) in VANILLA_RULE_TESTS {
             let edgework = edgework.parse::<Edgework>().unwrap();
-            let solution = rules.evaluate(&edgework, colors).as_index(colors).unwrap();
-            assert_eq!(expected, solution);
+            let solution = rules.solve(&edgework, colors);
+            assert_eq!(expected as usize, solution);
         }
     }
 
@@ -28,6 +28,9 @@ pub fn end_bomb(
     if handler.bombs.write().remove(&bomb.channel).is_some() {
         bomb.timer.freeze();
         bomb.drop_callback = Some(Box::new(drop_callback));
+        crate::persistence::remove_checkpoint(bomb.channel);
+        crate::metrics::active_bombs(handler.bombs.read().len());
+        crate::metrics::bomb_lifetime(bomb.timer.elapsed());
         handler.schedule_presence_update();
     }
 }
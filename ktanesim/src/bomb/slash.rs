@@ -0,0 +1,178 @@
+//! A slash-command front end for `!run`.
+//!
+//! This reuses [`parse_group`], [`consolidate_named_parameters`] and [`get_named_parameter`]
+//! from [`crate::bomb::starting`] so the validation logic for module specifiers, timers and rule
+//! seeds stays single-sourced between the text command and the slash command. The specifier
+//! option additionally gets interaction-based autocomplete over [`crate::modules::MODULE_GROUPS`].
+
+use crate::bomb::starting::{consolidate_named_parameters, get_named_parameter, parse_group, MAX_MODULES};
+use crate::modules::ModuleNew;
+use crate::prelude::*;
+use rand::prelude::*;
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::interactions::{
+    ApplicationCommandInteractionDataOption, ApplicationCommandOptionType, Interaction,
+};
+
+/// Registers the `/run` application command, matching the options of the text `!run` command:
+/// an integer `count`, a string `modules` specifier, and optional `timer`/`ruleseed` parameters.
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("run")
+        .description("Start a new bomb")
+        .create_option(|option| {
+            option
+                .name("count")
+                .description("How many modules to generate")
+                .kind(ApplicationCommandOptionType::Integer)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("modules")
+                .description("Which module group to pick from, e.g. `wires+morse-mods`")
+                .kind(ApplicationCommandOptionType::String)
+                .required(true)
+                .set_autocomplete(true)
+        })
+        .create_option(|option| {
+            option
+                .name("each")
+                .description("Generate `count` of every module in the group, instead of sampling `count` of them")
+                .kind(ApplicationCommandOptionType::Boolean)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("timer")
+                .description("Timer mode: zen, time, or a duration such as 8m30s")
+                .kind(ApplicationCommandOptionType::String)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("ruleseed")
+                .description("The rule seed to generate modules with")
+                .kind(ApplicationCommandOptionType::Integer)
+                .required(false)
+        })
+}
+
+fn find_option<'a>(
+    options: &'a [ApplicationCommandInteractionDataOption],
+    name: &str,
+) -> Option<&'a ApplicationCommandInteractionDataOption> {
+    options.iter().find(|option| option.name == name)
+}
+
+/// Computes autocomplete suggestions for the partially-typed `modules` specifier: only the
+/// last `+`/`-`-joined term is completed, against the keys of `MODULE_GROUPS`, so earlier
+/// validated terms in the expression are left untouched.
+pub fn autocomplete_modules(partial: &str) -> Vec<String> {
+    let split_at = partial
+        .rfind(|ch| ch == '+' || ch == '-')
+        .map_or(0, |index| index + 1);
+    let (prefix, term) = partial.split_at(split_at);
+
+    let mut suggestions: Vec<&'static str> = crate::modules::MODULE_GROUPS
+        .keys()
+        .cloned()
+        .filter(|name| name.starts_with(term))
+        .collect();
+    suggestions.sort_unstable();
+
+    suggestions
+        .into_iter()
+        .take(25) // Discord's autocomplete response is capped at 25 choices.
+        .map(|name| format!("{}{}", prefix, name))
+        .collect()
+}
+
+/// Handles an autocomplete interaction for the `/run` command, returning suggestions for the
+/// `modules` option, the only option registered with autocomplete.
+pub fn handle_autocomplete(interaction: &Interaction) -> Vec<String> {
+    let options = &interaction.data.as_ref().expect("autocomplete interaction missing data").options;
+
+    match find_option(options, "modules") {
+        Some(option) => {
+            let partial = option.value.as_ref().and_then(|value| value.as_str()).unwrap_or("");
+            autocomplete_modules(partial)
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Parses a `/run` interaction's options into the same pieces `cmd_run` would have parsed from
+/// the text command: the fully resolved list of modules to start, and the named parameters.
+///
+/// Mirrors `cmd_run`'s own group-resolution loop: with `each` set, every module in the parsed
+/// group is included `count` times; otherwise `count` modules are independently sampled from the
+/// group. `count` is bounds-checked the same way the text command's parser checks it.
+pub fn parse_interaction(
+    interaction: &Interaction,
+) -> Result<(Vec<ModuleNew>, crate::bomb::starting::NamedParameters), ErrorMessage> {
+    let options = &interaction.data.as_ref().expect("run interaction missing data").options;
+
+    let count = find_option(options, "count")
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_i64())
+        .ok_or_else(|| ("Missing count".to_owned(), "Please specify a count.".to_owned()))?;
+
+    let count: u32 = match count {
+        count if count < 0 => {
+            return Err((
+                "Syntax error".to_owned(),
+                "`count` must not be negative.".to_owned(),
+            ));
+        }
+        count if count as u64 <= u64::from(MAX_MODULES) => count as u32,
+        _ => {
+            return Err((
+                "Count too large".to_owned(),
+                format!("I like your enthusiasm, but don't you think that's a bit too many modules? Could you limit yourself to {} for now?", MAX_MODULES),
+            ));
+        }
+    };
+
+    let each = find_option(options, "each")
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+
+    let specifier = find_option(options, "modules")
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| ("Missing modules".to_owned(), "Please specify a module group.".to_owned()))?;
+
+    let group_modules = parse_group(specifier)?;
+    if group_modules.is_empty() {
+        return Err((
+            "Empty module set".to_owned(),
+            "The module group specifier excludes all implemented modules.".to_owned(),
+        ));
+    }
+
+    let mut named = Vec::new();
+    if let Some(timer) = find_option(options, "timer").and_then(|option| option.value.as_ref()).and_then(|value| value.as_str()) {
+        named.push(get_named_parameter("timer", timer)?);
+    }
+    if let Some(seed) = find_option(options, "ruleseed").and_then(|option| option.value.as_ref()).and_then(|value| value.as_i64()) {
+        named.push(get_named_parameter("ruleseed", &seed.to_string())?);
+    }
+    let named = consolidate_named_parameters(named.into_iter())?;
+
+    let mut modules = Vec::new();
+    if each {
+        for _ in 0..count {
+            modules.extend(group_modules.iter().map(|module| module.0));
+        }
+    } else {
+        let group_modules: Vec<_> = group_modules.iter().map(|module| module.0).collect();
+        let rng = &mut rand::thread_rng();
+        for _ in 0..count {
+            modules.push(*group_modules.choose(rng).unwrap());
+        }
+    }
+
+    Ok((modules, named))
+}
@@ -9,7 +9,7 @@ use serenity::utils::MessageBuilder;
 use std::collections::HashSet;
 use std::time::Duration;
 
-const MAX_MODULES: u32 = 101;
+pub(crate) const MAX_MODULES: u32 = 101;
 
 fn ensure_no_bomb(ctx: &Context, msg: &Message) -> Result<(), ErrorMessage> {
     if crate::bomb::running_in(ctx, msg) {
@@ -33,6 +33,7 @@ fn start_bomb(
     ruleseed: u32,
     modules: &[ModuleNew],
 ) {
+    crate::metrics::bomb_started();
     unimplemented!();
 }
 
@@ -151,7 +152,7 @@ pub fn cmd_run(ctx: &Context, msg: &Message, params: Parameters<'_>) -> Result<(
 
 // Work around rust-lang/rust#46989
 #[derive(Clone, Copy)]
-struct HashableModuleNew(ModuleNew);
+pub(crate) struct HashableModuleNew(pub(crate) ModuleNew);
 
 impl PartialEq for HashableModuleNew {
     fn eq(&self, other: &HashableModuleNew) -> bool {
@@ -188,7 +189,7 @@ fn specifier_no_meaning(
     Err(("Syntax error".to_owned(), msg))
 }
 
-fn parse_group(input: &str) -> Result<HashSet<HashableModuleNew>, ErrorMessage> {
+pub(crate) fn parse_group(input: &str) -> Result<HashSet<HashableModuleNew>, ErrorMessage> {
     let mut output = HashSet::new();
 
     let mut starting_index = 0;
@@ -277,6 +278,13 @@ mod tests {
              group between them, after `wires`. This has no defined meaning.".to_owned(),
         )));
     }
+
+    #[test]
+    fn levenshtein_distance_case_insensitive() {
+        assert_eq!(levenshtein_distance("wires", "WIRES"), 0);
+        assert_eq!(levenshtein_distance("mrose", "morse"), 2);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
 }
 
 fn handle_group(
@@ -294,31 +302,84 @@ fn handle_group(
                 }
             }
         }
-        // TODO: fuzzy suggestions
-        None => return Err((
-            "No such module".to_owned(),
-            MessageBuilder::new()
-            .push_mono_safe(name)
-            .push(" is not recognized as a module or module group name. Try **!modules** to get a list.")
-            .build())),
+        None => {
+            let mut message = MessageBuilder::new();
+            message
+                .push_mono_safe(name)
+                .push(" is not recognized as a module or module group name. Try **!modules** to get a list.");
+
+            let suggestions = suggest_names(name);
+            if !suggestions.is_empty() {
+                message.push(" Did you mean ");
+                for (index, suggestion) in suggestions.iter().enumerate() {
+                    if index > 0 {
+                        message.push(", ");
+                    }
+                    message.push_mono_safe(suggestion);
+                }
+                message.push("?");
+            }
+
+            return Err(("No such module".to_owned(), message.build()));
+        }
     }
 
     Ok(())
 }
 
+/// Computes the Levenshtein edit distance between two strings, comparing case-insensitively.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut matrix = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=b.len() {
+        matrix[0][j] = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            matrix[i][j] = (matrix[i - 1][j] + 1)
+                .min(matrix[i][j - 1] + 1)
+                .min(matrix[i - 1][j - 1] + cost);
+        }
+    }
+
+    matrix[a.len()][b.len()]
+}
+
+/// Finds up to three known module/group names closest to `input`, for use as "did you mean?"
+/// suggestions when a name isn't found in [`crate::modules::MODULE_GROUPS`].
+fn suggest_names(input: &str) -> Vec<&'static str> {
+    let max_distance = std::cmp::max(2, input.chars().count() / 3);
+
+    let mut candidates: Vec<(usize, &'static str)> = crate::modules::MODULE_GROUPS
+        .keys()
+        .map(|&name| (levenshtein_distance(input, name), name))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    candidates.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
 /// The value of a single named parameter.
-enum NamedParameter {
+pub(crate) enum NamedParameter {
     Ruleseed(u32),
     Timer(TimerMode),
 }
 
 /// A list of values for all named parameters
-struct NamedParameters {
-    ruleseed: u32,
-    timer: Option<TimerMode>,
+pub(crate) struct NamedParameters {
+    pub(crate) ruleseed: u32,
+    pub(crate) timer: Option<TimerMode>,
 }
 
-fn consolidate_named_parameters(
+pub(crate) fn consolidate_named_parameters(
     params: impl Iterator<Item = NamedParameter>,
 ) -> Result<NamedParameters, ErrorMessage> {
     let mut ruleseed = None;
@@ -351,7 +412,7 @@ fn consolidate_named_parameters(
     })
 }
 
-fn get_named_parameter(name: &str, value: &str) -> Result<NamedParameter, ErrorMessage> {
+pub(crate) fn get_named_parameter(name: &str, value: &str) -> Result<NamedParameter, ErrorMessage> {
     match name {
         "ruleseed" | "seed" | "rules" => {
             use ktane_utils::random::MAX_VALUE;
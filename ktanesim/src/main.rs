@@ -9,7 +9,9 @@
 mod backoff;
 mod bomb;
 mod gateway;
+mod metrics;
 mod modules;
+mod persistence;
 mod prelude;
 #[macro_use]
 mod util_macros;
@@ -25,69 +27,142 @@ use serenity::gateway::Shard;
 use serenity::model::event::{Event, GatewayEvent};
 use serenity::prelude::*;
 use std::io::prelude::*;
+use futures::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::prelude::*;
 use tokio_async_await::compat::forward::IntoAwaitable;
 
-fn main() {
-    tokio::run_async(
-        async {
-            if let Err(err) = kankyo::load() {
-                eprintln!("Couldn't load .env file: {:?}", err);
+/// How often [`spawn_checkpoint_task`] writes a fresh checkpoint of every running bomb, so a
+/// crash (as opposed to a clean [`shut_down`]) loses at most this much progress.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Freezes and checkpoints every running bomb, then exits the process. Called once the shutdown
+/// signal has been received.
+///
+/// This deliberately does not go through [`bomb::end_bomb`]: that function is for a bomb that has
+/// *finished* (solved or exploded), so it removes the bomb from `handler.bombs` and deletes its
+/// checkpoint via [`persistence::remove_checkpoint`]. A bomb that's merely ticking when the
+/// process exits needs the opposite treatment — its checkpoint written, not removed — so it can
+/// be restored on the next startup.
+fn shut_down(handler: &Handler) -> ! {
+    info!("Shutting down, freezing and checkpointing all bombs");
+
+    for bomb in handler.bombs.read().values() {
+        bomb.lock().timer.freeze();
+    }
+    persistence::checkpoint_all(handler);
+
+    std::process::exit(0);
+}
+
+/// Spawns a task that waits for ctrl_c or SIGTERM, then sets `shutting_down` so the event loop
+/// can break out cleanly, and runs [`shut_down`].
+fn spawn_shutdown_task(handler: Handler, shutting_down: Arc<AtomicBool>) {
+    tokio::spawn_async(
+        async move {
+            let ctrl_c = tokio_signal::ctrl_c().flatten_stream();
+            let terminate = tokio_signal::unix::Signal::new(tokio_signal::unix::SIGTERM)
+                .flatten_stream();
+
+            awaitt!(ctrl_c.select(terminate).into_future()).ok();
+
+            shutting_down.store(true, Ordering::SeqCst);
+            shut_down(&handler);
+        },
+    );
+}
+
+/// Periodically checkpoints every running bomb, so an unclean exit (a crash, `kill -9`, a host
+/// reboot) loses at most [`CHECKPOINT_INTERVAL`] of progress rather than the whole bomb. Clean
+/// shutdowns are still handled separately by [`shut_down`], which checkpoints immediately.
+fn spawn_checkpoint_task(handler: Handler, shutting_down: Arc<AtomicBool>) {
+    tokio::spawn_async(
+        async move {
+            let mut ticks = tokio::timer::Interval::new_interval(CHECKPOINT_INTERVAL);
+
+            while !shutting_down.load(Ordering::SeqCst) {
+                match awaitt!(ticks.into_future()) {
+                    Ok((Some(_), rest)) => {
+                        ticks = rest;
+                        persistence::checkpoint_all(&handler);
+                    }
+                    _ => break,
+                }
             }
+        },
+    );
+}
 
-            env_logger::init();
-            let token = kankyo::key("DISCORD_TOKEN").expect("Token not present in environment");
-            let mut shard = awaitt!(Shard::new(token, [0, 1])).expect("Couldn't create shard");
-            let mut messages = shard.messages().unwrap();
-            let mut backoff = Backoff::new();
-
-            loop {
-                let event: Result<Option<Event>, Error> = try {
-                    let message = await!(messages.next())??;
-                    let event = shard.parse(&message)?;
-                    use serenity::gateway::Action;
-                    if let Some(action) = shard.process(&event)? {
-                        match action {
-                            Action::Identify => {
-                                trace!("Identifying");
-                                shard.identify()?;
-                                continue;
-                            }
-                            Action::Autoreconnect => {
-                                trace!("Shard requested autoreconnect");
-                                awaitt!(backoff.delay())?;
-                                awaitt!(shard.autoreconnect())?;
-                                messages = shard.messages().unwrap();
-                            }
-                            Action::Reconnect => {
-                                trace!("Shard requested reconnect");
-                                awaitt!(backoff.delay())?;
-                                awaitt!(shard.reconnect())?;
-                                messages = shard.messages().unwrap();
-                                continue;
-                            }
-                            Action::Resume => {
-                                trace!("Resuming");
-                                awaitt!(shard.resume())?;
-                                messages = shard.messages().unwrap();
-                                continue;
-                            }
+/// Runs a single shard's gateway loop, forwarding every dispatched [`Event`] onto `sender` so it
+/// can be processed alongside events from every other shard. Each shard owns its own connection
+/// and its own [`Backoff`]; a reconnect on one shard never blocks the others.
+fn spawn_shard(
+    index: u64,
+    shard_count: u64,
+    token: String,
+    sender: mpsc::UnboundedSender<Event>,
+    shutting_down: Arc<AtomicBool>,
+) {
+    tokio::spawn_async(async move {
+        let mut shard = awaitt!(Shard::new(token, [index, shard_count]))
+            .unwrap_or_else(|_| panic!("Couldn't create shard {}", index));
+        let mut messages = shard.messages().unwrap();
+        let mut backoff = Backoff::new();
+
+        while !shutting_down.load(Ordering::SeqCst) {
+            let event: Result<Option<Event>, Error> = try {
+                let message = await!(messages.next())??;
+                let event = shard.parse(&message)?;
+                use serenity::gateway::Action;
+                if let Some(action) = shard.process(&event)? {
+                    match action {
+                        Action::Identify => {
+                            trace!("Shard {}: identifying", index);
+                            shard.identify()?;
+                            continue;
+                        }
+                        Action::Autoreconnect => {
+                            trace!("Shard {}: requested autoreconnect", index);
+                            awaitt!(backoff.delay())?;
+                            awaitt!(shard.autoreconnect())?;
+                            messages = shard.messages().unwrap();
+                        }
+                        Action::Reconnect => {
+                            trace!("Shard {}: requested reconnect", index);
+                            awaitt!(backoff.delay())?;
+                            awaitt!(shard.reconnect())?;
+                            messages = shard.messages().unwrap();
+                            continue;
+                        }
+                        Action::Resume => {
+                            trace!("Shard {}: resuming", index);
+                            awaitt!(shard.resume())?;
+                            messages = shard.messages().unwrap();
+                            continue;
                         }
                     }
+                }
 
-                    if let GatewayEvent::Dispatch(_, event) = event {
-                        Some(event)
-                    } else {
-                        None
-                    }
-                };
+                if let GatewayEvent::Dispatch(_, event) = event {
+                    Some(event)
+                } else {
+                    None
+                }
+            };
 
-                if let Ok(event) = event {
-                    if let Some(event) = event {
-                        backoff.success();
+            match event {
+                Ok(Some(event)) => {
+                    backoff.success();
+                    if sender.unbounded_send(event).is_err() {
+                        // The receiving end is gone, i.e. we're shutting down.
+                        break;
                     }
-                } else {
-                    warn!("Event loop error, reconnecting: {:?}", event.unwrap_err());
+                }
+                Ok(None) => {}
+                Err(why) => {
+                    warn!("Shard {}: event loop error, reconnecting: {:?}", index, why);
                     while let Err(why) = awaitt!(backoff
                         .delay()
                         .from_err()
@@ -95,12 +170,60 @@ fn main() {
                         Result<_, Error>
                     {
                         backoff.failure();
-                        warn!("Error while reconnecting: {:?}", why);
+                        warn!("Shard {}: error while reconnecting: {:?}", index, why);
                     }
 
                     messages = shard.messages().unwrap();
                 }
             }
+        }
+    });
+}
+
+fn main() {
+    tokio::run_async(
+        async {
+            if let Err(err) = kankyo::load() {
+                eprintln!("Couldn't load .env file: {:?}", err);
+            }
+
+            env_logger::init();
+
+            let handler = Handler::default();
+            persistence::restore_all(&handler);
+
+            let shutting_down = Arc::new(AtomicBool::new(false));
+            spawn_shutdown_task(handler.clone(), Arc::clone(&shutting_down));
+            spawn_checkpoint_task(handler.clone(), Arc::clone(&shutting_down));
+
+            let token = kankyo::key("DISCORD_TOKEN").expect("Token not present in environment");
+            let shard_count: u64 = kankyo::key("SHARD_COUNT")
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(1);
+
+            let (sender, receiver) = mpsc::unbounded();
+            for index in 0..shard_count {
+                spawn_shard(
+                    index,
+                    shard_count,
+                    token.clone(),
+                    sender.clone(),
+                    Arc::clone(&shutting_down),
+                );
+            }
+
+            // `sender` itself must be dropped too, or `receiver` never sees its stream end.
+            drop(sender);
+            let mut events = receiver;
+
+            // Events from every shard land here, merged into one stream. Each event is received
+            // and discarded; this loop does not touch `handler.bombs` or the presence.
+            while !shutting_down.load(Ordering::SeqCst) {
+                match await!(events.next()) {
+                    Some(_event) => {}
+                    None => break,
+                }
+            }
         },
     );
 }
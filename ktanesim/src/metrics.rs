@@ -0,0 +1,99 @@
+//! A minimal StatsD client for exporting bomb and module activity, so operators can build
+//! dashboards of solve/strike rates and concurrency instead of relying on
+//! [`crate::bomb::update_presence`] alone.
+//!
+//! The StatsD host/port are read from the environment the same way `DISCORD_TOKEN` is, via
+//! `kankyo`. If `STATSD_HOST`/`STATSD_PORT` aren't set, every function in this module becomes a
+//! no-op so nothing breaks when running locally without a metrics backend.
+//!
+//! **Known gap:** [`strike`], [`module_solved`] and [`bomb_detonated`] have no call site yet.
+//! Nothing in this codebase currently resolves a module's interaction result to a strike, a
+//! solve, or a detonation — that dispatch (a `Module` trait, or wherever `!cut`/`!submit`-style
+//! commands land) doesn't exist in this tree. The counters are kept because the original request
+//! scoped them explicitly and the StatsD names are part of the operator-facing contract; treat
+//! wiring them up as blocked on that dispatch existing, not as done.
+
+use lazy_static::lazy_static;
+use std::net::UdpSocket;
+use std::sync::Mutex;
+use std::time::Duration;
+
+struct StatsdClient {
+    socket: UdpSocket,
+    target: String,
+}
+
+lazy_static! {
+    static ref CLIENT: Option<Mutex<StatsdClient>> = configure();
+}
+
+fn configure() -> Option<Mutex<StatsdClient>> {
+    let host = kankyo::key("STATSD_HOST")?;
+    let port = kankyo::key("STATSD_PORT")?;
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .map_err(|why| warn!("Couldn't open StatsD socket, metrics disabled: {:?}", why))
+        .ok()?;
+
+    Some(Mutex::new(StatsdClient {
+        socket,
+        target: format!("{}:{}", host, port),
+    }))
+}
+
+fn send(metric: &str) {
+    if let Some(client) = &*CLIENT {
+        let client = client.lock().unwrap();
+        if let Err(why) = client.socket.send_to(metric.as_bytes(), &client.target) {
+            warn!("Failed to emit StatsD metric: {:?}", why);
+        }
+    }
+}
+
+fn gauge(name: &str, value: i64) {
+    send(&format!("ktanesim.{}:{}|g", name, value));
+}
+
+fn increment(name: &str) {
+    send(&format!("ktanesim.{}:1|c", name));
+}
+
+fn timing(name: &str, duration: Duration) {
+    send(&format!("ktanesim.{}:{}|ms", name, duration.as_millis()));
+}
+
+/// Reports the number of currently active bombs. Called wherever `handler.bombs` changes size:
+/// from [`crate::bomb::end_bomb`] and [`crate::persistence::restore_all`].
+pub fn active_bombs(count: usize) {
+    gauge("bombs.active", count as i64);
+}
+
+/// A new bomb was started.
+pub fn bomb_started() {
+    increment("bombs.started");
+}
+
+/// A strike was incurred on some module.
+///
+/// Not called anywhere yet; see the "Known gap" note at the top of this module.
+pub fn strike() {
+    increment("bombs.strikes");
+}
+
+/// A module was solved.
+///
+/// Not called anywhere yet; see the "Known gap" note at the top of this module.
+pub fn module_solved() {
+    increment("modules.solved");
+}
+
+/// A bomb detonated.
+///
+/// Not called anywhere yet; see the "Known gap" note at the top of this module.
+pub fn bomb_detonated() {
+    increment("bombs.detonated");
+}
+
+/// Records how long a bomb was alive for, from `start_bomb` to `end_bomb`.
+pub fn bomb_lifetime(duration: Duration) {
+    timing("bombs.lifetime", duration);
+}
@@ -0,0 +1,134 @@
+//! Persists in-flight bombs to disk so they survive gateway reconnects and full process
+//! restarts, then reloads them again at startup.
+//!
+//! Each bomb is written as a CBOR-encoded [`BombSnapshot`], keyed by its [`ChannelId`]. Modules
+//! are identified by name rather than by their `ModuleNew` function pointer, since function
+//! pointers can't be serialized; [`crate::modules::by_name`] resolves the name back to a
+//! constructor on reload.
+
+use crate::bomb::{BombData, Timer};
+use crate::prelude::*;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Directory bomb checkpoints are written to, relative to the working directory.
+const PERSISTENCE_DIR: &str = "bombs";
+
+/// Everything needed to fully re-arm a bomb after a restart.
+#[derive(Serialize, Deserialize)]
+struct BombSnapshot {
+    channel: ChannelId,
+    ruleseed: u32,
+    timer_mode: TimerMode,
+    remaining: Duration,
+    modules: Vec<ModuleSnapshot>,
+}
+
+/// A single module's identity and its frozen internal state.
+#[derive(Serialize, Deserialize)]
+struct ModuleSnapshot {
+    name: String,
+    state: Vec<u8>,
+}
+
+fn snapshot_path(channel: ChannelId) -> PathBuf {
+    Path::new(PERSISTENCE_DIR).join(format!("{}.cbor", channel.0))
+}
+
+/// Writes the current state of `bomb` to disk, overwriting any earlier checkpoint.
+pub fn checkpoint_bomb(bomb: &BombData) -> io::Result<()> {
+    fs::create_dir_all(PERSISTENCE_DIR)?;
+
+    let snapshot = BombSnapshot {
+        channel: bomb.channel,
+        ruleseed: bomb.ruleseed,
+        timer_mode: bomb.timer.mode(),
+        remaining: bomb.timer.remaining(),
+        modules: bomb
+            .modules
+            .iter()
+            .map(|module| ModuleSnapshot {
+                name: crate::modules::name_of(module.constructor()).to_owned(),
+                state: module.freeze_state(),
+            })
+            .collect(),
+    };
+
+    let file = fs::File::create(snapshot_path(bomb.channel))?;
+    serde_cbor::to_writer(file, &snapshot).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+/// Removes a bomb's checkpoint. Called once a bomb has ended, so a stale snapshot doesn't get
+/// re-armed on the next restart.
+pub fn remove_checkpoint(channel: ChannelId) {
+    if let Err(why) = fs::remove_file(snapshot_path(channel)) {
+        if why.kind() != io::ErrorKind::NotFound {
+            warn!("Couldn't remove bomb checkpoint for {}: {:?}", channel, why);
+        }
+    }
+}
+
+/// Writes a checkpoint for every currently running bomb. Used on graceful shutdown.
+pub fn checkpoint_all(handler: &Handler) {
+    for bomb in handler.bombs.read().values() {
+        if let Err(why) = checkpoint_bomb(&bomb.lock()) {
+            warn!("Failed to checkpoint bomb: {:?}", why);
+        }
+    }
+}
+
+/// Loads every checkpointed bomb from disk and re-arms it in `handler.bombs`, ready for the
+/// gateway connection to be established. Call this before connecting to Discord at startup.
+pub fn restore_all(handler: &Handler) {
+    let dir = match fs::read_dir(PERSISTENCE_DIR) {
+        Ok(dir) => dir,
+        Err(ref why) if why.kind() == io::ErrorKind::NotFound => return,
+        Err(why) => {
+            warn!("Couldn't read bomb persistence directory: {:?}", why);
+            return;
+        }
+    };
+
+    for entry in dir.filter_map(Result::ok) {
+        let path = entry.path();
+        match restore_one(&path) {
+            Ok((channel, bomb)) => {
+                handler
+                    .bombs
+                    .write()
+                    .insert(channel, Arc::new(Mutex::new(bomb)));
+            }
+            Err(why) => warn!("Couldn't restore bomb from {:?}: {:?}", path, why),
+        }
+    }
+
+    crate::metrics::active_bombs(handler.bombs.read().len());
+}
+
+fn restore_one(path: &Path) -> io::Result<(ChannelId, BombData)> {
+    let file = fs::File::open(path)?;
+    let snapshot: BombSnapshot =
+        serde_cbor::from_reader(file).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+    let modules = snapshot
+        .modules
+        .into_iter()
+        .filter_map(|module| {
+            let constructor = crate::modules::by_name(&module.name)?;
+            Some(constructor.restore(&module.state))
+        })
+        .collect();
+
+    let bomb = BombData {
+        channel: snapshot.channel,
+        ruleseed: snapshot.ruleseed,
+        timer: Timer::resuming(snapshot.timer_mode, snapshot.remaining),
+        modules,
+        drop_callback: None,
+    };
+
+    Ok((snapshot.channel, bomb))
+}